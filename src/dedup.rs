@@ -0,0 +1,198 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+static RUNS_OF_SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r" {2,}").unwrap());
+
+/// Granularity at which `dedup_content` considers two pieces of content "the same",
+/// configurable via `Config::dedup_granularity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupGranularity {
+    /// Split on blank lines and on ATX heading starts.
+    Block,
+    /// Split only on ATX heading starts, keeping each heading's whole section together.
+    HeadingSection,
+}
+
+impl Default for DedupGranularity {
+    fn default() -> Self {
+        DedupGranularity::Block
+    }
+}
+
+/// Removes blocks whose normalized content hash has already been seen (tracked via
+/// `seen`, shared across files so dedup applies across the whole combined document).
+/// Returns the deduped content and the number of blocks elided.
+pub fn dedup_content(
+    content: &str,
+    granularity: DedupGranularity,
+    seen: &mut FxHashSet<u64>,
+) -> (String, usize) {
+    let mut kept = String::new();
+    let mut elided = 0;
+
+    for block in split_into_blocks(content, granularity) {
+        let normalized = normalize_block(&block);
+        if normalized.is_empty() {
+            continue;
+        }
+        let hash = fxhash_str(&normalized);
+        if !seen.insert(hash) {
+            elided += 1;
+            continue;
+        }
+        kept.push_str(&block);
+        if !kept.ends_with("\n\n") {
+            kept.push('\n');
+        }
+    }
+
+    (kept, elided)
+}
+
+fn is_fence_marker(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Splits `content` into blocks on blank-line and/or heading boundaries, per
+/// `granularity`. Fenced code blocks (``` / ~~~) suspend boundary detection while open,
+/// so a `# comment` inside a bash example isn't mistaken for an ATX heading and doesn't
+/// fracture the surrounding block into a hash-collision-prone sliver.
+fn split_into_blocks(content: &str, granularity: DedupGranularity) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if is_fence_marker(line) {
+            in_fence = !in_fence;
+            current.push_str(line);
+            current.push('\n');
+            continue;
+        }
+        if in_fence {
+            current.push_str(line);
+            current.push('\n');
+            continue;
+        }
+
+        let is_heading = line.trim_start().starts_with('#');
+        let is_blank = line.trim().is_empty();
+
+        if is_heading && !current.trim().is_empty() {
+            blocks.push(std::mem::take(&mut current));
+        }
+        if granularity == DedupGranularity::Block && is_blank && !current.trim().is_empty() {
+            blocks.push(std::mem::take(&mut current));
+            continue;
+        }
+
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+fn normalize_block(block: &str) -> String {
+    block
+        .lines()
+        .map(|line| RUNS_OF_SPACES.replace_all(line.trim_end(), " ").to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn fxhash_str(value: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = rustc_hash::FxHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_granularity_splits_on_blank_lines_and_headings() {
+        let content = "# Heading one\nLine a\n\nLine b\n# Heading two\nLine c\n";
+        let blocks = split_into_blocks(content, DedupGranularity::Block);
+        assert_eq!(
+            blocks,
+            vec![
+                "# Heading one\nLine a\n".to_string(),
+                "Line b\n".to_string(),
+                "# Heading two\nLine c\n".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn heading_section_granularity_ignores_blank_lines() {
+        let content = "# Heading one\nLine a\n\nLine b\n# Heading two\nLine c\n";
+        let blocks = split_into_blocks(content, DedupGranularity::HeadingSection);
+        assert_eq!(
+            blocks,
+            vec![
+                "# Heading one\nLine a\n\nLine b\n".to_string(),
+                "# Heading two\nLine c\n".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn fenced_code_blocks_suspend_heading_and_blank_line_splitting() {
+        let content = "# Real heading\nIntro\n```bash\n# Configure the environment\nexport X=1\n\necho done\n```\nOutro\n";
+        let blocks = split_into_blocks(content, DedupGranularity::Block);
+        assert_eq!(
+            blocks,
+            vec![
+                "# Real heading\nIntro\n```bash\n# Configure the environment\nexport X=1\n\necho done\n```\nOutro\n"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn dedup_content_elides_repeated_blocks_and_counts_them() {
+        let mut seen = FxHashSet::default();
+        let first = "# Heading\nSame text\n";
+        let (kept_first, elided_first) = dedup_content(first, DedupGranularity::Block, &mut seen);
+        assert_eq!(elided_first, 0);
+        assert!(kept_first.contains("Same text"));
+
+        let second = "# Heading\nSame text\n\nNew text\n";
+        let (kept_second, elided_second) = dedup_content(second, DedupGranularity::Block, &mut seen);
+        assert_eq!(elided_second, 1);
+        assert!(!kept_second.contains("Same text"));
+        assert!(kept_second.contains("New text"));
+    }
+
+    #[test]
+    fn dedup_content_does_not_elide_a_shared_comment_line_inside_distinct_fenced_examples() {
+        // Both examples share an identical `# Configure the environment` comment
+        // surrounded by blank lines, which (pre-fence-tracking) would be mistaken for
+        // an isolated, hashable block and wrongly elided from the second file even
+        // though the surrounding fenced examples are not duplicates of each other.
+        let mut seen = FxHashSet::default();
+        let bash_example_a =
+            "```bash\nexport FOO=1\n\n# Configure the environment\n\necho setup-a\n```\n";
+        let bash_example_b =
+            "```bash\nexport BAR=2\n\n# Configure the environment\n\necho setup-b\n```\n";
+
+        let (kept_a, elided_a) = dedup_content(bash_example_a, DedupGranularity::Block, &mut seen);
+        let (kept_b, elided_b) = dedup_content(bash_example_b, DedupGranularity::Block, &mut seen);
+
+        assert_eq!(elided_a, 0);
+        assert_eq!(elided_b, 0);
+        assert!(kept_b.contains("echo setup-b"));
+        assert!(kept_a.contains("echo setup-a"));
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Lookup-optimized snapshot of a directory's immediate contents, built in a single
+/// `read_dir` pass. Modeled on starship's `Context` dir scan: instead of repeatedly
+/// checking `dir.join("x").exists()`, we gather the file names once and query the
+/// in-memory set.
+#[derive(Debug, Default)]
+pub struct DirContents {
+    file_names: HashSet<String>,
+    is_git_repo: bool,
+}
+
+impl DirContents {
+    fn scan(dir: &Path) -> Self {
+        let mut contents = DirContents::default();
+
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    contents.file_names.insert(name.to_string());
+                }
+            }
+        }
+
+        contents.is_git_repo = git2::Repository::discover(dir).is_ok();
+        contents
+    }
+
+    pub fn has_file(&self, name: &str) -> bool {
+        self.file_names.contains(name)
+    }
+}
+
+static DIR_CONTENTS_CACHE: Lazy<Mutex<HashMap<PathBuf, Arc<DirContents>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the `DirContents` for `dir`, scanning each distinct directory at most once
+/// per run; repeated lookups for an already-seen `dir` are free.
+pub fn dir_contents(dir: &Path) -> Arc<DirContents> {
+    let mut cache = DIR_CONTENTS_CACHE.lock().unwrap();
+    cache
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| Arc::new(DirContents::scan(dir)))
+        .clone()
+}
+
+/// Inspects `target_dir` and returns the set of convention tags its detected stack
+/// suggests, e.g. a `Cargo.toml` suggests the `rust` tag.
+pub fn detect_tags(target_dir: &Path) -> Result<Vec<String>> {
+    let contents = dir_contents(target_dir);
+    let mut tags = Vec::new();
+
+    if contents.has_file("Cargo.toml") {
+        tags.push("rust".to_string());
+    }
+    if contents.has_file("package.json") {
+        tags.push("node".to_string());
+        tags.push("web".to_string());
+    }
+    if contents.has_file("pyproject.toml") || contents.has_file("requirements.txt") {
+        tags.push("python".to_string());
+    }
+    if contents.is_git_repo {
+        tags.push("git-workflow".to_string());
+    }
+
+    tags.sort();
+    tags.dedup();
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "llmresources-detect-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn dir_contents_cache_keeps_distinct_directories_independent() {
+        let rust_dir = temp_dir();
+        fs::write(rust_dir.join("Cargo.toml"), "").unwrap();
+
+        let node_dir = temp_dir();
+        fs::write(node_dir.join("package.json"), "").unwrap();
+
+        let rust_contents = dir_contents(&rust_dir);
+        let node_contents = dir_contents(&node_dir);
+
+        assert!(rust_contents.has_file("Cargo.toml"));
+        assert!(!rust_contents.has_file("package.json"));
+
+        assert!(node_contents.has_file("package.json"));
+        assert!(!node_contents.has_file("Cargo.toml"));
+
+        // Re-fetching the first directory must still reflect its own contents, not
+        // whatever was scanned for the second directory in between.
+        let rust_contents_again = dir_contents(&rust_dir);
+        assert!(rust_contents_again.has_file("Cargo.toml"));
+        assert!(!rust_contents_again.has_file("package.json"));
+    }
+
+    #[test]
+    fn detect_tags_no_longer_infers_python_or_docker_from_loose_files() {
+        let dir = temp_dir();
+        fs::write(dir.join("app.py"), "").unwrap();
+        fs::write(dir.join("Dockerfile"), "").unwrap();
+
+        let tags = detect_tags(&dir).unwrap();
+        assert!(!tags.contains(&"python".to_string()));
+        assert!(!tags.contains(&"docker".to_string()));
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::ConventionFile;
+
+/// A single rule in `Config::discovery_rules`. Every discovered `*.md` file is checked
+/// against the rules in order; the first rule that matches contributes its `tags` to the
+/// file's `ConventionFile::tags`. A file that matches no rule gets no tags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryRule {
+    pub name_regex: Option<String>,
+    pub path_glob: Option<String>,
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(skip)]
+    compiled: OnceCell<CompiledRule>,
+}
+
+impl Default for DiscoveryRule {
+    fn default() -> Self {
+        Self {
+            name_regex: None,
+            path_glob: Some("**/*.md".to_string()),
+            max_depth: None,
+            tags: Vec::new(),
+            compiled: OnceCell::new(),
+        }
+    }
+}
+
+/// The rule's `path_glob`/`name_regex`, compiled once and reused across every file
+/// checked against this rule, instead of recompiling per file.
+#[derive(Debug, Default, Clone)]
+struct CompiledRule {
+    path_glob: Option<glob::Pattern>,
+    name_regex: Option<Regex>,
+}
+
+impl DiscoveryRule {
+    fn compiled(&self) -> Result<&CompiledRule> {
+        self.compiled.get_or_try_init(|| {
+            let path_glob = self
+                .path_glob
+                .as_deref()
+                .map(|pattern| {
+                    glob::Pattern::new(pattern)
+                        .with_context(|| format!("Invalid path_glob: {}", pattern))
+                })
+                .transpose()?;
+            let name_regex = self
+                .name_regex
+                .as_deref()
+                .map(|pattern| {
+                    Regex::new(pattern).with_context(|| format!("Invalid name_regex: {}", pattern))
+                })
+                .transpose()?;
+            Ok(CompiledRule { path_glob, name_regex })
+        })
+    }
+
+    fn matches(&self, relative_path: &Path, depth: usize) -> Result<bool> {
+        if let Some(max_depth) = self.max_depth {
+            if depth > max_depth {
+                return Ok(false);
+            }
+        }
+        let compiled = self.compiled()?;
+        if let Some(glob) = &compiled.path_glob {
+            if !glob.matches_path(relative_path) {
+                return Ok(false);
+            }
+        }
+        if let Some(re) = &compiled.name_regex {
+            let name = relative_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !re.is_match(&name) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Recursively walks `conventions/`, matching every `*.md` file it finds against `rules`
+/// to determine its tags. Replaces the old flat, top-level-only scan.
+pub fn find_convention_files(rules: &[DiscoveryRule]) -> Result<Vec<ConventionFile>> {
+    let conventions_dir = Path::new("conventions");
+    if !conventions_dir.exists() {
+        anyhow::bail!("conventions/ directory not found");
+    }
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(conventions_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() || path.extension().map_or(true, |ext| ext != "md") {
+            continue;
+        }
+        let relative = path.strip_prefix(conventions_dir).unwrap_or(path);
+        let depth = entry.depth();
+
+        let tags = tags_for(rules, relative, depth)?;
+        log::debug!("Discovered {} (tags: {:?})", relative.display(), tags);
+        files.push(ConventionFile::with_tags(path.to_path_buf(), tags));
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    log::info!("Found {} convention file(s)", files.len());
+    Ok(files)
+}
+
+fn tags_for(rules: &[DiscoveryRule], relative_path: &Path, depth: usize) -> Result<Vec<String>> {
+    for rule in rules {
+        if rule.matches(relative_path, depth)? {
+            return Ok(rule.tags.clone());
+        }
+    }
+    Ok(Vec::new())
+}
+
+pub fn default_rules() -> Vec<DiscoveryRule> {
+    vec![DiscoveryRule::default()]
+}
+
+pub fn deduped_tags(files: &[ConventionFile]) -> Vec<String> {
+    let mut tags: Vec<String> = files.iter().flat_map(|f| f.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Returns the subset of `files` carrying `tag`, preserving their original order.
+pub fn files_tagged<'a>(files: &'a [ConventionFile], tag: &str) -> Vec<&'a ConventionFile> {
+    files.iter().filter(|f| f.tags.iter().any(|t| t == tag)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(path_glob: Option<&str>, max_depth: Option<usize>, tags: &[&str]) -> DiscoveryRule {
+        DiscoveryRule {
+            name_regex: None,
+            path_glob: path_glob.map(str::to_string),
+            max_depth,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            compiled: OnceCell::new(),
+        }
+    }
+
+    #[test]
+    fn file_matching_no_rule_gets_no_tags() {
+        let rules = vec![rule(Some("rust/**/*.md"), None, &["rust"])];
+        let tags = tags_for(&rules, Path::new("python/style.md"), 1).unwrap();
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn first_matching_rule_wins_over_a_later_also_matching_rule() {
+        let rules = vec![
+            rule(Some("**/*.md"), None, &["generic"]),
+            rule(Some("rust/**/*.md"), None, &["rust"]),
+        ];
+        let tags = tags_for(&rules, Path::new("rust/style.md"), 1).unwrap();
+        assert_eq!(tags, vec!["generic".to_string()]);
+    }
+
+    #[test]
+    fn max_depth_excludes_deeper_files() {
+        let rules = vec![rule(Some("**/*.md"), Some(1), &["shallow"])];
+
+        let shallow = tags_for(&rules, Path::new("style.md"), 1).unwrap();
+        assert_eq!(shallow, vec!["shallow".to_string()]);
+
+        let deep = tags_for(&rules, Path::new("a/b/style.md"), 2).unwrap();
+        assert!(deep.is_empty());
+    }
+
+    #[test]
+    fn compiled_pattern_is_reused_across_repeated_matches() {
+        let r = rule(Some("**/*.md"), None, &["rust"]);
+        assert!(r.matches(Path::new("a.md"), 0).unwrap());
+        assert!(r.matches(Path::new("b.md"), 0).unwrap());
+        assert!(r.compiled.get().is_some(), "pattern should be compiled and cached after first match");
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INCLUDE_DIRECTIVE: &str = "@include";
+const INCLUDE_IF_DIRECTIVE: &str = "@includeIf";
+
+/// Reads `path` and resolves any `@include` / `@includeIf` directive lines it contains,
+/// recursively splicing in the referenced file's (also-resolved) content in place of the
+/// directive line. Paths in directives are resolved relative to the including file's
+/// directory.
+pub fn resolve_includes(path: &Path, target_dir: &Path) -> Result<String> {
+    let mut stack = HashSet::new();
+    resolve_includes_inner(path, target_dir, &mut stack)
+}
+
+fn resolve_includes_inner(
+    path: &Path,
+    target_dir: &Path,
+    stack: &mut HashSet<PathBuf>,
+) -> Result<String> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+
+    if !stack.insert(canonical.clone()) {
+        log::warn!("Include cycle detected at {}, skipping", path.display());
+        return Ok(String::new());
+    }
+
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut resolved = String::new();
+    let mut in_fence = false;
+    for line in raw.lines() {
+        if is_fence_marker(line) {
+            in_fence = !in_fence;
+            resolved.push_str(line);
+            resolved.push('\n');
+            continue;
+        }
+        if in_fence {
+            resolved.push_str(line);
+            resolved.push('\n');
+            continue;
+        }
+
+        if let Some((condition, included)) = parse_include_if(line) {
+            if condition_matches(&condition, target_dir)? {
+                let included_path = base_dir.join(included);
+                log::debug!("Including {} (condition '{}' matched)", included_path.display(), condition);
+                resolved.push_str(&resolve_includes_inner(&included_path, target_dir, stack)?);
+                if !resolved.ends_with('\n') {
+                    resolved.push('\n');
+                }
+            } else {
+                log::debug!("Skipping include (condition '{}' did not match)", condition);
+            }
+        } else if let Some(included) = parse_include(line) {
+            let included_path = base_dir.join(included);
+            log::debug!("Including {}", included_path.display());
+            resolved.push_str(&resolve_includes_inner(&included_path, target_dir, stack)?);
+            if !resolved.ends_with('\n') {
+                resolved.push('\n');
+            }
+        } else {
+            resolved.push_str(line);
+            resolved.push('\n');
+        }
+    }
+
+    stack.remove(&canonical);
+    Ok(resolved)
+}
+
+fn is_fence_marker(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+fn parse_include(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix(INCLUDE_DIRECTIVE)?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim())
+}
+
+fn parse_include_if(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix(INCLUDE_IF_DIRECTIVE)?.trim();
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let condition = parts.next()?.to_string();
+    let included = parts.next()?.trim().to_string();
+    Some((condition, included))
+}
+
+fn condition_matches(condition: &str, target_dir: &Path) -> Result<bool> {
+    if let Some(pattern) = condition.strip_prefix("targetdir:") {
+        let glob = glob::Pattern::new(pattern)
+            .with_context(|| format!("Invalid targetdir glob: {}", pattern))?;
+        // Anchored to the final path component only: matching the whole path would let
+        // an unrelated ancestor directory (e.g. "/home/trusty-user/project") falsely
+        // match a glob like "*rust*" just because the substring appears higher up.
+        let name = target_dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        return Ok(glob.matches(&name));
+    }
+    log::warn!("Unrecognised @includeIf condition '{}', skipping", condition);
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "llmresources-includes-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_include_does_not_match_include_if_lines() {
+        assert_eq!(parse_include("@includeIf targetdir:*rust* shared/rust.md"), None);
+        assert_eq!(parse_include("@include shared/common.md"), Some("shared/common.md"));
+    }
+
+    #[test]
+    fn parse_include_if_extracts_condition_and_path() {
+        let (condition, path) = parse_include_if("@includeIf targetdir:*rust* shared/rust.md").unwrap();
+        assert_eq!(condition, "targetdir:*rust*");
+        assert_eq!(path, "shared/rust.md");
+    }
+
+    #[test]
+    fn resolve_includes_splices_plain_include() {
+        let dir = temp_dir();
+        fs::write(dir.join("shared.md"), "Shared rule\n").unwrap();
+        fs::write(dir.join("main.md"), "Intro\n@include shared.md\nOutro\n").unwrap();
+
+        let resolved = resolve_includes(&dir.join("main.md"), &dir).unwrap();
+        assert_eq!(resolved, "Intro\nShared rule\nOutro\n");
+    }
+
+    #[test]
+    fn resolve_includes_respects_include_if_targetdir_condition() {
+        let dir = temp_dir();
+        let rust_target = dir.join("my-rust-project");
+        fs::create_dir_all(&rust_target).unwrap();
+
+        fs::write(dir.join("rust.md"), "Rust rules\n").unwrap();
+        fs::write(
+            dir.join("main.md"),
+            "Intro\n@includeIf targetdir:*rust* rust.md\nOutro\n",
+        )
+        .unwrap();
+
+        let matched = resolve_includes(&dir.join("main.md"), &rust_target).unwrap();
+        assert_eq!(matched, "Intro\nRust rules\nOutro\n");
+
+        let unmatched = resolve_includes(&dir.join("main.md"), &dir.join("other")).unwrap();
+        assert_eq!(unmatched, "Intro\nOutro\n");
+    }
+
+    #[test]
+    fn resolve_includes_breaks_cycles() {
+        let dir = temp_dir();
+        fs::write(dir.join("a.md"), "A\n@include b.md\n").unwrap();
+        fs::write(dir.join("b.md"), "B\n@include a.md\n").unwrap();
+
+        let resolved = resolve_includes(&dir.join("a.md"), &dir).unwrap();
+        assert_eq!(resolved, "A\nB\n");
+    }
+
+    #[test]
+    fn condition_matches_is_anchored_to_the_final_path_component() {
+        // An ancestor component containing "rust" as a substring ("trusty-user") must
+        // not cause "*rust*" to match a target dir whose own name doesn't contain it.
+        let false_positive_shaped = Path::new("/home/trusty-user/project");
+        assert!(!condition_matches("targetdir:*rust*", false_positive_shaped).unwrap());
+
+        let real_match = Path::new("/home/trusty-user/my-rust-app");
+        assert!(condition_matches("targetdir:*rust*", real_match).unwrap());
+    }
+
+    #[test]
+    fn resolve_includes_ignores_directives_inside_fenced_code_blocks() {
+        let dir = temp_dir();
+        fs::write(
+            dir.join("main.md"),
+            "Intro\n```md\n@include nonexistent.md\n```\nOutro\n",
+        )
+        .unwrap();
+
+        let resolved = resolve_includes(&dir.join("main.md"), &dir).unwrap();
+        assert_eq!(
+            resolved,
+            "Intro\n```md\n@include nonexistent.md\n```\nOutro\n"
+        );
+    }
+}
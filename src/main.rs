@@ -6,17 +6,29 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod dedup;
+mod detect;
+mod discovery;
+mod includes;
+mod reader;
+mod shell;
+
+use dedup::DedupGranularity;
+use discovery::DiscoveryRule;
+
 #[derive(Debug, Clone)]
 struct ConventionFile {
     name: String,
     path: PathBuf,
+    tags: Vec<String>,
 }
 
 impl ConventionFile {
-    fn new(path: PathBuf) -> Self {
+    fn with_tags(path: PathBuf, tags: Vec<String>) -> Self {
         Self {
             name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
             path,
+            tags,
         }
     }
 }
@@ -24,85 +36,156 @@ impl ConventionFile {
 #[derive(Debug, Serialize, Deserialize)]
 struct Config {
     search_root: String,
+    #[serde(default = "discovery::default_rules")]
+    discovery_rules: Vec<DiscoveryRule>,
+    #[serde(default)]
+    dedup_granularity: DedupGranularity,
+    #[serde(default = "reader::default_thread_count")]
+    reader_threads: usize,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             search_root: "/Users/dave/Code".to_string(),
+            discovery_rules: discovery::default_rules(),
+            dedup_granularity: DedupGranularity::default(),
+            reader_threads: reader::default_thread_count(),
         }
     }
 }
 
 fn main() -> Result<()> {
+    init_logger();
     print_banner();
-    
+
+    let config = load_or_create_config()?;
+
     // 1. Find available convention files
-    let convention_files = find_convention_files()?;
+    let convention_files = discovery::find_convention_files(&config.discovery_rules)?;
     if convention_files.is_empty() {
         println!("{}", style("No .md files found in conventions/ directory").red());
         return Ok(());
     }
-    
-    // 2. User selects which files to include
-    let selected_files = select_convention_files(&convention_files)?;
+
+    if std::env::args().any(|arg| arg == "--shell") {
+        return shell::run(convention_files, &config);
+    }
+
+    let allow_duplicates = std::env::args().any(|arg| arg == "--allow-duplicates");
+
+    // 2. User selects target directory
+    let target_dir = get_target_directory(&config)?;
+
+    // 3. Detect the target's stack and pre-select matching conventions
+    let detected_tags = detect::detect_tags(&target_dir)?;
+    if !detected_tags.is_empty() {
+        println!(
+            "{}",
+            style(format!("Detected: {}", detected_tags.join(", "))).cyan()
+        );
+    }
+
+    // 4. Let the user bulk-adjust the selection by tag before the final pick
+    let chosen_tags = choose_tags(&convention_files, &detected_tags)?;
+
+    // 5. User selects which files to include, pre-checked by chosen tags
+    let selected_files = select_convention_files(&convention_files, &chosen_tags)?;
     if selected_files.is_empty() {
         println!("{}", style("No files selected. Exiting.").yellow());
         return Ok(());
     }
-    
-    // 3. User selects target directory
-    let target_dir = get_target_directory()?;
-    
-    // 4. Show summary and confirm
+
+    // 6. Show summary and confirm
     show_summary(&selected_files, &target_dir);
     if !confirm_proceed()? {
         println!("{}", style("Operation cancelled.").yellow());
         return Ok(());
     }
-    
-    // 5. Generate the file
-    generate_agents_file(&selected_files, &target_dir)?;
-    
+
+    // 7. Generate the file
+    generate_agents_file(
+        &selected_files,
+        &target_dir,
+        config.dedup_granularity,
+        allow_duplicates,
+        config.reader_threads,
+    )?;
+
     Ok(())
 }
 
+fn init_logger() {
+    let args: Vec<String> = std::env::args().collect();
+    let level = if args.iter().any(|a| a == "--quiet") {
+        log::LevelFilter::Error
+    } else if args.iter().any(|a| a == "--verbose") {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format_timestamp(None)
+        .format_target(false)
+        .init();
+}
+
 fn print_banner() {
     println!("{}", style("Convention Compiler").blue().bold());
     println!("{}", style("===================").blue());
     println!();
 }
 
-fn find_convention_files() -> Result<Vec<ConventionFile>> {
-    let conventions_dir = Path::new("conventions");
-    if !conventions_dir.exists() {
-        anyhow::bail!("conventions/ directory not found");
+/// Lets the user bulk-toggle whole tags (e.g. "rust", "backend") instead of ticking
+/// every matching file individually, pre-checked with whatever stack detection found.
+fn choose_tags(files: &[ConventionFile], detected_tags: &[String]) -> Result<Vec<String>> {
+    let tags = discovery::deduped_tags(files);
+    if tags.is_empty() {
+        return Ok(detected_tags.to_vec());
     }
-    
-    let mut files = Vec::new();
-    for entry in fs::read_dir(conventions_dir)? {
-        let path = entry?.path();
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "md") {
-            files.push(ConventionFile::new(path));
-        }
-    }
-    files.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(files)
+
+    let defaults: Vec<bool> = tags.iter().map(|t| detected_tags.contains(t)).collect();
+
+    let selections = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select tags to bulk-include (pre-checked from stack detection)")
+        .items(&tags)
+        .defaults(&defaults)
+        .interact()?;
+
+    Ok(selections.into_iter().map(|i| tags[i].clone()).collect())
 }
 
-fn select_convention_files(files: &[ConventionFile]) -> Result<Vec<ConventionFile>> {
-    let file_names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
-    
+fn select_convention_files(
+    files: &[ConventionFile],
+    preselect_tags: &[String],
+) -> Result<Vec<ConventionFile>> {
+    let labels: Vec<String> = files
+        .iter()
+        .map(|f| {
+            if f.tags.is_empty() {
+                f.name.clone()
+            } else {
+                format!("{} [{}]", f.name, f.tags.join(", "))
+            }
+        })
+        .collect();
+
+    let defaults: Vec<bool> = files
+        .iter()
+        .map(|f| f.tags.iter().any(|t| preselect_tags.contains(t)))
+        .collect();
+
     let selections = MultiSelect::with_theme(&ColorfulTheme::default())
         .with_prompt("Select convention files to combine")
-        .items(&file_names)
+        .items(&labels)
+        .defaults(&defaults)
         .interact()?;
-    
+
     Ok(selections.into_iter().map(|i| files[i].clone()).collect())
 }
 
-fn get_target_directory() -> Result<PathBuf> {
-    let config = load_or_create_config()?;
+fn get_target_directory(config: &Config) -> Result<PathBuf> {
     let search_root = PathBuf::from(&config.search_root);
     
     // Gather candidate directories
@@ -198,27 +281,62 @@ fn confirm_proceed() -> Result<bool> {
         .context("Confirmation failed")
 }
 
-fn generate_agents_file(files: &[ConventionFile], target_dir: &Path) -> Result<()> {
+/// Reads `files` concurrently (see `reader::read_files_parallel`) and, unless
+/// `allow_duplicates` is set, elides any block whose normalized content has already
+/// been emitted. Dedup itself stays single-threaded and runs in selection order, since
+/// which file "wins" a duplicate depends on that order. Returns the rendered content
+/// plus the number of duplicate blocks elided.
+fn render_content(
+    files: &[ConventionFile],
+    target_dir: &Path,
+    granularity: DedupGranularity,
+    allow_duplicates: bool,
+    reader_threads: usize,
+) -> Result<(String, usize)> {
+    let file_contents = reader::read_files_parallel(files, target_dir, reader_threads)?;
+
     let mut content = String::new();
-    
-    for file in files {
-        let file_content = fs::read_to_string(&file.path)
-            .with_context(|| format!("Failed to read {}", file.name))?;
-            
+    let mut seen = rustc_hash::FxHashSet::default();
+    let mut elided = 0;
+
+    for mut file_content in file_contents {
+        if !allow_duplicates {
+            let (deduped, file_elided) = dedup::dedup_content(&file_content, granularity, &mut seen);
+            file_content = deduped;
+            elided += file_elided;
+        }
+
         content.push_str(&file_content);
         // Ensure clean separation
         if !content.ends_with('\n') { content.push('\n'); }
         if !content.ends_with("\n\n") { content.push('\n'); }
     }
 
+    Ok((content.trim().to_string(), elided))
+}
+
+fn generate_agents_file(
+    files: &[ConventionFile],
+    target_dir: &Path,
+    granularity: DedupGranularity,
+    allow_duplicates: bool,
+    reader_threads: usize,
+) -> Result<()> {
+    let (content, elided) =
+        render_content(files, target_dir, granularity, allow_duplicates, reader_threads)?;
+
     if !target_dir.exists() {
         println!("{}", style(format!("Creating directory: {}", target_dir.display())).yellow());
         fs::create_dir_all(target_dir)?;
     }
 
     let output_path = target_dir.join("AGENTS.md");
-    fs::write(&output_path, content.trim())?;
-    
+    fs::write(&output_path, content)?;
+    log::info!("Wrote {}", output_path.display());
+
+    if elided > 0 {
+        log::info!("Elided {} duplicate block(s)", elided);
+    }
     println!("{}", style("✓ AGENTS.md created successfully!").green().bold());
     Ok(())
 }
@@ -0,0 +1,140 @@
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::{includes, ConventionFile};
+
+pub fn default_thread_count() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
+/// Reads and resolves `files` concurrently across up to `thread_count` worker threads,
+/// then reassembles the results in the original selection order so output stays
+/// deterministic regardless of which thread finishes first.
+pub fn read_files_parallel(
+    files: &[ConventionFile],
+    target_dir: &Path,
+    thread_count: usize,
+) -> Result<Vec<String>> {
+    let progress = ProgressBar::new(files.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+
+    let queue: VecDeque<(usize, ConventionFile)> = files
+        .iter()
+        .cloned()
+        .enumerate()
+        .collect();
+    let queue = Arc::new(Mutex::new(queue));
+    let results: Arc<Mutex<Vec<Option<String>>>> = Arc::new(Mutex::new(vec![None; files.len()]));
+    let error: Arc<Mutex<Option<anyhow::Error>>> = Arc::new(Mutex::new(None));
+
+    let worker_count = thread_count.max(1).min(files.len().max(1));
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let queue = Arc::clone(&queue);
+            let results = Arc::clone(&results);
+            let error = Arc::clone(&error);
+            let progress = progress.clone();
+
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap().pop_front();
+                let Some((index, file)) = next else {
+                    break;
+                };
+
+                log::debug!("Reading {}", file.name);
+                match includes::resolve_includes(&file.path, target_dir)
+                    .with_context(|| format!("Failed to read {}", file.name))
+                {
+                    Ok(content) => {
+                        results.lock().unwrap()[index] = Some(content);
+                    }
+                    Err(err) => {
+                        error.lock().unwrap().get_or_insert(err);
+                    }
+                }
+                progress.inc(1);
+            });
+        }
+    });
+
+    progress.finish_and_clear();
+
+    if let Some(err) = error.lock().unwrap().take() {
+        return Err(err);
+    }
+
+    let ordered: Vec<String> = results
+        .lock()
+        .unwrap()
+        .iter()
+        .cloned()
+        .map(|entry| entry.unwrap_or_default())
+        .collect();
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "llmresources-reader-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file(dir: &Path, name: &str, tags: &[&str]) -> ConventionFile {
+        ConventionFile {
+            name: name.to_string(),
+            path: dir.join(name),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn read_files_parallel_preserves_original_order_with_limited_threads() {
+        let dir = temp_dir();
+        let mut files = Vec::new();
+        for i in 0..8 {
+            let name = format!("{}.md", i);
+            fs::write(dir.join(&name), format!("content-{}\n", i)).unwrap();
+            files.push(file(&dir, &name, &[]));
+        }
+
+        let contents = read_files_parallel(&files, &dir, 2).unwrap();
+        let expected: Vec<String> = (0..8).map(|i| format!("content-{}\n", i)).collect();
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn read_files_parallel_returns_error_when_a_file_is_missing() {
+        let dir = temp_dir();
+        let mut files = Vec::new();
+        for i in 0..4 {
+            let name = format!("{}.md", i);
+            if i != 2 {
+                fs::write(dir.join(&name), format!("content-{}\n", i)).unwrap();
+            }
+            files.push(file(&dir, &name, &[]));
+        }
+
+        let result = read_files_parallel(&files, &dir, 4);
+        assert!(result.is_err(), "a missing file should surface as an error, not be silently dropped");
+    }
+}
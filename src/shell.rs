@@ -0,0 +1,282 @@
+use anyhow::Result;
+use console::style;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::discovery;
+use crate::{generate_agents_file, render_content, Config, ConventionFile};
+
+/// Interactive REPL for browsing convention files and assembling an `AGENTS.md`,
+/// modeled on proxmox's catalog shell. An alternative to the linear wizard in `main`.
+pub fn run(all_files: Vec<ConventionFile>, config: &Config) -> Result<()> {
+    let mut target_dir = PathBuf::from(".");
+    let mut staged: Vec<ConventionFile> = Vec::new();
+    let mut rl = DefaultEditor::new()?;
+
+    println!("{}", style("Convention Compiler shell").blue().bold());
+    println!("Commands: ls, cd <dir>, add <name|tag:<tag>>, rm <name>, preview, diff, write, exit");
+
+    loop {
+        let prompt = format!("[{}] > ", target_dir.display());
+        match rl.readline(&prompt) {
+            Ok(line) => {
+                let _ = rl.add_history_entry(line.as_str());
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let mut parts = line.splitn(2, char::is_whitespace);
+                let cmd = parts.next().unwrap_or("");
+                let arg = parts.next().unwrap_or("").trim();
+
+                match cmd {
+                    "ls" => cmd_ls(&all_files),
+                    "cd" => cmd_cd(arg, &mut target_dir),
+                    "add" => cmd_add(arg, &all_files, &mut staged),
+                    "rm" => cmd_rm(arg, &mut staged),
+                    "preview" => cmd_preview(&staged, &target_dir, config)?,
+                    "diff" => cmd_diff(&staged, &target_dir, config)?,
+                    "write" => cmd_write(&staged, &target_dir, config)?,
+                    "exit" | "quit" => break,
+                    other => println!("{}", style(format!("Unknown command: {}", other)).red()),
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                println!("{}", style(format!("Readline error: {}", err)).red());
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_ls(all_files: &[ConventionFile]) {
+    for file in all_files {
+        if file.tags.is_empty() {
+            println!("  {}", file.name);
+        } else {
+            println!("  {} [{}]", file.name, file.tags.join(", "));
+        }
+    }
+}
+
+fn cmd_cd(arg: &str, target_dir: &mut PathBuf) {
+    if arg.is_empty() {
+        println!("{}", style("Usage: cd <dir>").yellow());
+        return;
+    }
+    let candidate = PathBuf::from(arg);
+    let resolved = if candidate.is_absolute() {
+        candidate
+    } else {
+        target_dir.join(candidate)
+    };
+    if resolved.is_dir() {
+        *target_dir = resolved;
+    } else {
+        println!("{}", style(format!("Not a directory: {}", arg)).red());
+    }
+}
+
+fn cmd_add(arg: &str, all_files: &[ConventionFile], staged: &mut Vec<ConventionFile>) {
+    if arg.is_empty() {
+        println!("{}", style("Usage: add <name> | add tag:<tag>").yellow());
+        return;
+    }
+
+    if let Some(tag) = arg.strip_prefix("tag:") {
+        let matches = discovery::files_tagged(all_files, tag);
+        if matches.is_empty() {
+            println!("{}", style(format!("No files tagged '{}'", tag)).red());
+            return;
+        }
+        let mut added = 0;
+        for file in matches {
+            if !staged.iter().any(|f| f.name == file.name) {
+                staged.push(file.clone());
+                added += 1;
+            }
+        }
+        println!("{}", style(format!("Staged {} file(s) tagged '{}'", added, tag)).green());
+        return;
+    }
+
+    match all_files.iter().find(|f| f.name == arg) {
+        Some(file) if !staged.iter().any(|f| f.name == file.name) => {
+            staged.push(file.clone());
+        }
+        Some(_) => println!("{}", style(format!("{} already staged", arg)).yellow()),
+        None => println!("{}", style(format!("No such convention file: {}", arg)).red()),
+    }
+}
+
+fn cmd_rm(arg: &str, staged: &mut Vec<ConventionFile>) {
+    if arg.is_empty() {
+        println!("{}", style("Usage: rm <name>").yellow());
+        return;
+    }
+    let before = staged.len();
+    staged.retain(|f| f.name != arg);
+    if staged.len() == before {
+        println!("{}", style(format!("{} is not staged", arg)).yellow());
+    }
+}
+
+fn cmd_preview(staged: &[ConventionFile], target_dir: &PathBuf, config: &Config) -> Result<()> {
+    if staged.is_empty() {
+        println!("{}", style("Nothing staged yet").yellow());
+        return Ok(());
+    }
+    for file in staged {
+        println!("{}", style(format!("--- {} ---", file.name)).cyan());
+    }
+    let (content, elided) = render_content(
+        staged,
+        target_dir,
+        config.dedup_granularity,
+        false,
+        config.reader_threads,
+    )?;
+    println!("{}", content);
+    if elided > 0 {
+        println!("{}", style(format!("({} duplicate block(s) elided)", elided)).yellow());
+    }
+    Ok(())
+}
+
+fn cmd_diff(staged: &[ConventionFile], target_dir: &PathBuf, config: &Config) -> Result<()> {
+    let (new_content, _) = render_content(
+        staged,
+        target_dir,
+        config.dedup_granularity,
+        false,
+        config.reader_threads,
+    )?;
+    let existing_path = target_dir.join("AGENTS.md");
+    let old_content = fs::read_to_string(&existing_path).unwrap_or_default();
+
+    let diff = TextDiff::from_lines(&old_content, &new_content);
+    for change in diff.iter_all_changes() {
+        let (sign, colored) = match change.tag() {
+            ChangeTag::Delete => ("-", style(change.to_string()).red()),
+            ChangeTag::Insert => ("+", style(change.to_string()).green()),
+            ChangeTag::Equal => (" ", style(change.to_string())),
+        };
+        print!("{}{}", sign, colored);
+    }
+    Ok(())
+}
+
+fn cmd_write(staged: &[ConventionFile], target_dir: &PathBuf, config: &Config) -> Result<()> {
+    if staged.is_empty() {
+        println!("{}", style("Nothing staged yet").yellow());
+        return Ok(());
+    }
+    generate_agents_file(
+        staged,
+        target_dir,
+        config.dedup_granularity,
+        false,
+        config.reader_threads,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "llmresources-shell-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file(name: &str, tags: &[&str]) -> ConventionFile {
+        ConventionFile {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn cmd_add_stages_by_name_and_skips_duplicates() {
+        let all_files = vec![file("rust.md", &[])];
+        let mut staged = Vec::new();
+
+        cmd_add("rust.md", &all_files, &mut staged);
+        assert_eq!(staged.len(), 1);
+
+        cmd_add("rust.md", &all_files, &mut staged);
+        assert_eq!(staged.len(), 1, "re-adding an already-staged file should be a no-op");
+    }
+
+    #[test]
+    fn cmd_add_tag_stages_every_file_with_that_tag_once() {
+        let all_files = vec![
+            file("rust.md", &["rust"]),
+            file("node.md", &["node"]),
+            file("rust-web.md", &["rust", "web"]),
+        ];
+        let mut staged = Vec::new();
+
+        cmd_add("tag:rust", &all_files, &mut staged);
+        assert_eq!(staged.len(), 2);
+        assert!(staged.iter().any(|f| f.name == "rust.md"));
+        assert!(staged.iter().any(|f| f.name == "rust-web.md"));
+
+        cmd_add("tag:rust", &all_files, &mut staged);
+        assert_eq!(staged.len(), 2, "re-adding the same tag should not duplicate entries");
+    }
+
+    #[test]
+    fn cmd_rm_removes_a_staged_file_by_name() {
+        let mut staged = vec![file("rust.md", &[]), file("node.md", &[])];
+
+        cmd_rm("rust.md", &mut staged);
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].name, "node.md");
+
+        cmd_rm("not-staged.md", &mut staged);
+        assert_eq!(staged.len(), 1, "removing a file that isn't staged should be a no-op");
+    }
+
+    #[test]
+    fn cmd_cd_resolves_relative_paths_against_target_dir_across_multiple_hops() {
+        let root = temp_dir();
+        let child = root.join("child");
+        let grandchild = child.join("grandchild");
+        fs::create_dir_all(&grandchild).unwrap();
+
+        let mut target_dir = root.clone();
+        cmd_cd("child", &mut target_dir);
+        assert_eq!(target_dir, child);
+
+        cmd_cd("grandchild", &mut target_dir);
+        assert_eq!(target_dir, grandchild);
+
+        cmd_cd("..", &mut target_dir);
+        assert_eq!(target_dir, grandchild.join(".."));
+    }
+
+    #[test]
+    fn cmd_cd_rejects_non_directories() {
+        let root = temp_dir();
+        let mut target_dir = root.clone();
+
+        cmd_cd("does-not-exist", &mut target_dir);
+        assert_eq!(target_dir, root, "an invalid target should leave target_dir unchanged");
+    }
+}